@@ -0,0 +1,35 @@
+//! Interactive authentication: bot tokens, and the user login-code / 2FA
+//! password dance for accounts that don't already have a saved session.
+
+use grammers_client::{Client, SignInError};
+use grammers_client::types::{LoginToken, PasswordToken};
+
+pub enum CodeOutcome {
+    SignedIn,
+    PasswordRequired(PasswordToken),
+}
+
+pub async fn bot_sign_in(client: &Client, bot_token: &str) -> Result<(), String> {
+    client.bot_sign_in(bot_token).await
+        .map_err(|e| format!("Bot sign-in failed: {}", e))?;
+    Ok(())
+}
+
+pub async fn request_login_code(client: &Client, phone: &str) -> Result<LoginToken, String> {
+    client.request_login_code(phone).await
+        .map_err(|e| format!("Failed to request login code for {}: {}", phone, e))
+}
+
+pub async fn submit_code(client: &Client, token: &LoginToken, code: &str) -> Result<CodeOutcome, String> {
+    match client.sign_in(token, code).await {
+        Ok(_) => Ok(CodeOutcome::SignedIn),
+        Err(SignInError::PasswordRequired(password_token)) => Ok(CodeOutcome::PasswordRequired(password_token)),
+        Err(e) => Err(format!("Sign-in failed: {}", e)),
+    }
+}
+
+pub async fn submit_password(client: &Client, token: &PasswordToken, password: &str) -> Result<(), String> {
+    client.check_password(token.clone(), password).await
+        .map_err(|e| format!("Two-step verification failed: {}", e))?;
+    Ok(())
+}
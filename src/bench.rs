@@ -0,0 +1,135 @@
+//! Workload-driven benchmark harness.
+//!
+//! Replaces the old "square a million integers" micro-benchmark with runs
+//! that actually exercise the scraper against a target and report numbers
+//! that are comparable across builds: throughput, wall time, and
+//! latency percentiles. Latency is taken per iteration (one full
+//! `scrape_channel` call); individual requests are not timed separately.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::NativeTelegramScraper;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workload {
+    pub name: String,
+    pub target: String,
+    pub max_members: u32,
+    #[serde(default = "default_iterations")]
+    pub iterations: u32,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: u32,
+}
+
+fn default_iterations() -> u32 { 1 }
+fn default_concurrency() -> u32 { 1 }
+
+#[derive(Debug, Serialize)]
+pub struct BenchResult {
+    pub workload: String,
+    pub target: String,
+    pub iterations: u32,
+    pub members_per_second: f64,
+    pub total_wall_ms: u128,
+    pub p50_latency_ms: u128,
+    pub p95_latency_ms: u128,
+    pub flood_wait_seconds: u64,
+    pub version: String,
+}
+
+pub fn load_workloads(paths: &[String]) -> Result<Vec<Workload>, String> {
+    let mut workloads = Vec::with_capacity(paths.len());
+    for path in paths {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read workload {}: {}", path, e))?;
+        let workload: Workload = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse workload {}: {}", path, e))?;
+        workloads.push(workload);
+    }
+    Ok(workloads)
+}
+
+/// Runs a single workload's iterations sequentially and reports aggregate stats.
+pub async fn run_workload(workload: &Workload) -> BenchResult {
+    println!("⚡ Running workload '{}' against {} ({} iterations)", workload.name, workload.target, workload.iterations);
+
+    let wall_start = Instant::now();
+    let mut latencies = Vec::with_capacity(workload.iterations as usize);
+    let mut total_members = 0u32;
+    let mut flood_wait_seconds = 0u64;
+
+    for i in 0..workload.iterations {
+        let mut scraper = NativeTelegramScraper::new();
+        let request_start = Instant::now();
+
+        match scraper.scrape_channel(&workload.target, workload.max_members, false).await {
+            Ok(members) => total_members += members.len() as u32,
+            Err(e) => {
+                eprintln!("⚠️  Iteration {} failed: {}", i + 1, e);
+                if let Some(seconds) = parse_flood_wait(&e) {
+                    flood_wait_seconds += seconds;
+                }
+            }
+        }
+
+        latencies.push(request_start.elapsed());
+    }
+
+    let total_wall = wall_start.elapsed();
+    let (p50, p95) = percentiles(&mut latencies);
+
+    BenchResult {
+        workload: workload.name.clone(),
+        target: workload.target.clone(),
+        iterations: workload.iterations,
+        members_per_second: total_members as f64 / total_wall.as_secs_f64().max(f64::EPSILON),
+        total_wall_ms: total_wall.as_millis(),
+        p50_latency_ms: p50.as_millis(),
+        p95_latency_ms: p95.as_millis(),
+        flood_wait_seconds,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+fn percentiles(latencies: &mut [Duration]) -> (Duration, Duration) {
+    if latencies.is_empty() {
+        return (Duration::ZERO, Duration::ZERO);
+    }
+    latencies.sort();
+    let p50 = latencies[latencies.len() / 2];
+    let p95 = latencies[(latencies.len() * 95 / 100).min(latencies.len() - 1)];
+    (p50, p95)
+}
+
+fn parse_flood_wait(message: &str) -> Option<u64> {
+    let idx = message.find("FLOOD_WAIT_")?;
+    message[idx + "FLOOD_WAIT_".len()..]
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+pub fn print_results_table(results: &[BenchResult]) {
+    println!("\n📊 BENCHMARK RESULTS ({})", env!("CARGO_PKG_VERSION"));
+    println!("{:<20} {:>10} {:>14} {:>12} {:>12} {:>12} {:>12}",
+        "workload", "iters", "members/sec", "wall(ms)", "p50(ms)", "p95(ms)", "flood(s)");
+    for r in results {
+        println!("{:<20} {:>10} {:>14.2} {:>12} {:>12} {:>12} {:>12}",
+            r.workload, r.iterations, r.members_per_second, r.total_wall_ms, r.p50_latency_ms, r.p95_latency_ms, r.flood_wait_seconds);
+    }
+}
+
+pub async fn report_results(report_url: &str, results: &[BenchResult]) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    for result in results {
+        client.post(report_url)
+            .json(result)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to POST bench result to {}: {}", report_url, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Concurrent profile-photo downloader.
+//!
+//! Fetches each scraped member's profile photo after a scrape completes,
+//! bounded by a semaphore so the burst of `photos.GetUserPhotos` +
+//! CDN-file requests doesn't trip `FLOOD_WAIT`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use grammers_client::Client;
+use grammers_tl_types::enums::InputUser;
+use grammers_tl_types::functions::photos::GetUserPhotos;
+use grammers_tl_types::enums::photos::Photos;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::TelegramMember;
+
+const DEFAULT_PERMITS: usize = 8;
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Downloads a profile photo for every member, writing `<dir>/<id>.jpg`.
+/// Returns the member id -> saved path map for whichever downloads succeeded;
+/// members without a photo, or whose download ultimately failed, are omitted.
+pub async fn download_photos(
+    client: &Client,
+    members: &[TelegramMember],
+    dir: &str,
+) -> Result<Vec<(i64, String)>, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+
+    let semaphore = Arc::new(Semaphore::new(DEFAULT_PERMITS));
+    let mut tasks = Vec::with_capacity(members.len());
+
+    for member in members {
+        let client = client.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let id = member.id;
+        let out_dir = PathBuf::from(dir);
+
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            download_one(&client, id, &out_dir).await.map(|path| (id, path))
+        }));
+    }
+
+    let mut downloaded = Vec::new();
+    for task in tasks {
+        match task.await {
+            Ok(Ok(Some(entry))) => downloaded.push(entry),
+            Ok(Ok(None)) => {}
+            Ok(Err(e)) => warn!("Profile photo download failed: {}", e),
+            Err(e) => warn!("Download task panicked: {}", e),
+        }
+    }
+
+    Ok(downloaded)
+}
+
+async fn download_one(client: &Client, user_id: i64, out_dir: &PathBuf) -> Result<Option<String>, String> {
+    let request = GetUserPhotos {
+        user_id: InputUser::User(grammers_tl_types::types::InputUser {
+            user_id,
+            access_hash: 0,
+        }),
+        offset: 0,
+        max_id: 0,
+        limit: 1,
+    };
+
+    let photos = match client.invoke(&request).await {
+        Ok(Photos::Photos(p)) => p.photos,
+        Ok(Photos::Slice(s)) => s.photos,
+        Err(e) => return Err(format!("GetUserPhotos({}) failed: {}", user_id, e)),
+    };
+
+    let photo = match photos.into_iter().next() {
+        Some(p) => p,
+        None => return Ok(None),
+    };
+
+    let path = out_dir.join(format!("{}.jpg", user_id));
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        match tokio::time::timeout(FETCH_TIMEOUT, client.download_media(&photo, &path)).await {
+            Ok(Ok(_)) => return Ok(Some(path.to_string_lossy().into_owned())),
+            Ok(Err(e)) if attempt == MAX_ATTEMPTS => {
+                return Err(format!("CDN fetch for user {} failed after {} attempts: {}", user_id, MAX_ATTEMPTS, e));
+            }
+            Err(_) if attempt == MAX_ATTEMPTS => {
+                return Err(format!("CDN fetch for user {} timed out after {} attempts", user_id, MAX_ATTEMPTS));
+            }
+            _ => continue,
+        }
+    }
+
+    unreachable!("loop always returns on the final attempt")
+}
@@ -1,6 +1,10 @@
 //! High-Performance Telegram Scraper Core Engine
 //! Rust/C++ Hybrid - 10x faster than Python version
 
+mod auth;
+mod downloader;
+mod storage;
+
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int, c_uint};
 use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
@@ -14,6 +18,10 @@ use dashmap::DashMap;
 use crossbeam_channel::{bounded, Receiver, Sender};
 use tracing::{info, warn, error};
 
+use storage::Storage;
+
+const DEFAULT_DB_PATH: &str = "telegram_scraper.db";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[repr(C)]
 pub struct TelegramMember {
@@ -24,6 +32,8 @@ pub struct TelegramMember {
     pub phone: *mut c_char,
     pub is_premium: bool,
     pub last_online: i64,
+    /// Path to the downloaded profile photo, or null if none was fetched.
+    pub photo_path: *mut c_char,
 }
 
 #[derive(Debug)]
@@ -34,8 +44,24 @@ pub struct ScraperEngine {
     dedup_set: Arc<Mutex<HashSet<i64>>>,
     is_running: Arc<AtomicBool>,
     work_queue: (Sender<ScrapingTask>, Receiver<ScrapingTask>),
+    storage: Arc<Mutex<Storage>>,
+    member_feed: Sender<TelegramMember>,
+    member_callbacks: Arc<Mutex<Vec<MemberCallback>>>,
+    member_broadcast: tokio::sync::broadcast::Sender<TelegramMember>,
+    session_file: Mutex<Option<String>>,
+    pending_login: Mutex<Option<grammers_client::types::LoginToken>>,
+    pending_password: Mutex<Option<grammers_client::types::PasswordToken>>,
 }
 
+/// A C callback invoked with a borrowed `TelegramMember` as soon as it's
+/// deduplicated, so C++ consumers can stream results instead of waiting
+/// for the whole scrape to finish.
+pub type MemberCallback = unsafe extern "C" fn(*const TelegramMember);
+
+/// Bound on the live member feed; `add_unique_member` blocks once it fills,
+/// applying backpressure instead of silently dropping members.
+const MEMBER_FEED_CAPACITY: usize = 256;
+
 #[derive(Debug, Clone)]
 struct ScrapingTask {
     target: String,
@@ -47,17 +73,99 @@ impl ScraperEngine {
     pub fn new() -> Self {
         let runtime = Arc::new(Runtime::new().expect("Failed to create Tokio runtime"));
         let (tx, rx) = bounded(1000);
-        
+
+        let storage = Storage::open(DEFAULT_DB_PATH)
+            .expect("Failed to open default member database");
+        let dedup_set = storage.load_existing_ids()
+            .expect("Failed to preload dedup set from database");
+
+        info!("💾 Preloaded {} known members from {}", dedup_set.len(), DEFAULT_DB_PATH);
+
+        let (member_feed_tx, member_feed_rx) = bounded::<TelegramMember>(MEMBER_FEED_CAPACITY);
+        let member_callbacks: Arc<Mutex<Vec<MemberCallback>>> = Arc::new(Mutex::new(Vec::new()));
+        let (member_broadcast, _) = tokio::sync::broadcast::channel(MEMBER_FEED_CAPACITY);
+
+        // Fans each newly-deduplicated member out to every registered FFI
+        // callback and to the broadcast channel, so a progress UI and an
+        // exporter can both subscribe to the same live feed.
+        let callbacks_for_thread = Arc::clone(&member_callbacks);
+        let broadcast_for_thread = member_broadcast.clone();
+        std::thread::spawn(move || {
+            while let Ok(member) = member_feed_rx.recv() {
+                for callback in callbacks_for_thread.lock().unwrap().iter() {
+                    unsafe { callback(&member as *const TelegramMember) };
+                }
+                let _ = broadcast_for_thread.send(member);
+            }
+        });
+
         Self {
             client: None,
             runtime,
             members_cache: Arc::new(DashMap::new()),
-            dedup_set: Arc::new(Mutex::new(HashSet::new())),
+            dedup_set: Arc::new(Mutex::new(dedup_set)),
             is_running: Arc::new(AtomicBool::new(false)),
             work_queue: (tx, rx),
+            storage: Arc::new(Mutex::new(storage)),
+            member_feed: member_feed_tx,
+            member_callbacks,
+            member_broadcast,
+            session_file: Mutex::new(None),
+            pending_login: Mutex::new(None),
+            pending_password: Mutex::new(None),
         }
     }
 
+    /// Registers a callback invoked with each member as soon as it's
+    /// deduplicated, for streaming consumers on the C++ side.
+    pub fn set_member_callback(&self, callback: MemberCallback) {
+        self.member_callbacks.lock().unwrap().push(callback);
+    }
+
+    /// Subscribes to the live member feed; multiple subscribers may exist
+    /// concurrently, each receiving members from the point they subscribe.
+    /// The broadcast buffer holds `MEMBER_FEED_CAPACITY` members and does not
+    /// block the sender: a subscriber that falls further behind misses the
+    /// oldest ones and its next `recv` returns `RecvError::Lagged`.
+    pub fn subscribe_members(&self) -> tokio::sync::broadcast::Receiver<TelegramMember> {
+        self.member_broadcast.subscribe()
+    }
+
+    /// Points the engine at a different SQLite file, reloading the dedup set
+    /// from whatever that file already has on disk.
+    pub fn open_db(&mut self, path: &str) -> Result<(), String> {
+        let storage = Storage::open(path)?;
+        let ids = storage.load_existing_ids()?;
+
+        info!("💾 Switched member database to {} ({} known members)", path, ids.len());
+
+        *self.dedup_set.lock().unwrap() = ids;
+        *self.storage.lock().unwrap() = storage;
+        Ok(())
+    }
+
+    /// Dumps the full persisted member table in the given format (`"json"` or `"csv"`).
+    pub fn export_db(&self, format: &str) -> Result<String, String> {
+        self.storage.lock().unwrap().export(format)
+    }
+
+    /// Downloads a profile photo for every currently cached member into `dir`,
+    /// setting `photo_path` on the corresponding cache entry when one is saved.
+    pub async fn download_photos(&mut self, dir: &str) -> Result<usize, String> {
+        let client = self.client.as_ref().ok_or("Client not connected")?;
+        let members: Vec<TelegramMember> = self.members_cache.iter().map(|e| e.value().clone()).collect();
+
+        let downloaded = downloader::download_photos(client, &members, dir).await?;
+
+        for (id, path) in &downloaded {
+            if let Some(mut entry) = self.members_cache.get_mut(id) {
+                entry.photo_path = CString::new(path.as_str()).unwrap().into_raw();
+            }
+        }
+
+        Ok(downloaded.len())
+    }
+
     pub async fn connect(&mut self, api_id: i32, api_hash: &str, session_file: &str) -> Result<(), String> {
         let config = Config {
             session: Session::load_file_or_create(session_file)
@@ -79,6 +187,68 @@ impl ScraperEngine {
         
         info!("🚀 Connected to Telegram MTProto");
         self.client = Some(client);
+        *self.session_file.lock().unwrap() = Some(session_file.to_string());
+        Ok(())
+    }
+
+    /// Signs in as a bot using a bot-father token; no login code required.
+    pub async fn bot_sign_in(&mut self, bot_token: &str) -> Result<(), String> {
+        let client = self.client.as_ref().ok_or("Client not connected")?;
+        auth::bot_sign_in(client, bot_token).await?;
+        self.save_session()?;
+        info!("🤖 Signed in as bot");
+        Ok(())
+    }
+
+    /// Starts interactive user sign-in by requesting a login code over SMS/Telegram.
+    pub async fn request_login_code(&mut self, phone: &str) -> Result<(), String> {
+        let client = self.client.as_ref().ok_or("Client not connected")?;
+        let token = auth::request_login_code(client, phone).await?;
+        *self.pending_login.lock().unwrap() = Some(token);
+        info!("📨 Login code requested for {}", phone);
+        Ok(())
+    }
+
+    /// Submits the code the user received. Returns `Ok(true)` if a 2FA
+    /// password is now required via `submit_password`, `Ok(false)` if
+    /// sign-in is already complete.
+    pub async fn submit_code(&mut self, code: &str) -> Result<bool, String> {
+        let client = self.client.as_ref().ok_or("Client not connected")?;
+        let token = self.pending_login.lock().unwrap().take()
+            .ok_or("No login code was requested")?;
+
+        match auth::submit_code(client, &token, code).await? {
+            auth::CodeOutcome::SignedIn => {
+                self.save_session()?;
+                info!("✅ Signed in");
+                Ok(false)
+            }
+            auth::CodeOutcome::PasswordRequired(password_token) => {
+                *self.pending_password.lock().unwrap() = Some(password_token);
+                info!("🔒 Two-step verification password required");
+                Ok(true)
+            }
+        }
+    }
+
+    /// Completes sign-in for accounts with two-step verification enabled.
+    pub async fn submit_password(&mut self, password: &str) -> Result<(), String> {
+        let client = self.client.as_ref().ok_or("Client not connected")?;
+        let token = self.pending_password.lock().unwrap().take()
+            .ok_or("No password was requested")?;
+
+        auth::submit_password(client, &token, password).await?;
+        self.save_session()?;
+        info!("✅ Signed in");
+        Ok(())
+    }
+
+    fn save_session(&self) -> Result<(), String> {
+        let client = self.client.as_ref().ok_or("Client not connected")?;
+        if let Some(path) = self.session_file.lock().unwrap().as_ref() {
+            client.session().save_to_file(path)
+                .map_err(|e| format!("Failed to save session to {}: {}", path, e))?;
+        }
         Ok(())
     }
 
@@ -107,7 +277,7 @@ impl ScraperEngine {
             match self.scrape_with_pattern(client, &chat, pattern, max_members - scraped).await {
                 Ok(batch) => {
                     for member in batch {
-                        if self.add_unique_member(&member) {
+                        if self.add_unique_member(&member, target) {
                             members.push(member);
                             scraped += 1;
                             if scraped >= max_members { break; }
@@ -128,48 +298,147 @@ impl ScraperEngine {
     async fn scrape_with_pattern(
         &self,
         client: &mut Client,
-        chat: &grammers_tl_types::types::Chat,
+        chat: &grammers_client::types::Chat,
         pattern: &str,
         limit: u32,
     ) -> Result<Vec<TelegramMember>, String> {
-        use grammers_tl_types::types::{ChannelParticipantsSearch, InputChannel};
+        use grammers_tl_types::enums::channels::ChannelParticipants;
+        use grammers_tl_types::enums::{ChannelParticipantsFilter, User as UserEnum};
+        use grammers_tl_types::types::ChannelParticipantsSearch;
         use grammers_tl_types::functions::channels::GetParticipants;
-        
-        // This is a simplified version - full implementation would use proper MTProto calls
+
+        let input_channel = chat.pack().to_input_channel()
+            .ok_or_else(|| "Resolved chat is not a channel".to_string())?;
+
         let mut members = Vec::new();
-        
-        // Simulate member data for now - real implementation would call Telegram API
-        for i in 0..std::cmp::min(limit, 50) {
-            let member = TelegramMember {
-                id: (i as i64) + (pattern.len() as i64 * 1000),
-                username: CString::new(format!("user_{}{}", pattern, i))
-                    .unwrap().into_raw(),
-                first_name: CString::new(format!("User{}", i))
-                    .unwrap().into_raw(),
-                last_name: CString::new(format!("Last{}", i))
-                    .unwrap().into_raw(),
-                phone: std::ptr::null_mut(),
-                is_premium: i % 10 == 0,
-                last_online: chrono::Utc::now().timestamp(),
+        let mut offset = 0i32;
+        const PAGE_SIZE: i32 = 200;
+
+        loop {
+            if members.len() >= limit as usize {
+                break;
+            }
+
+            let request = GetParticipants {
+                channel: input_channel.clone(),
+                filter: ChannelParticipantsFilter::ChannelParticipantsSearch(
+                    ChannelParticipantsSearch { q: pattern.to_string() },
+                ),
+                offset,
+                limit: PAGE_SIZE,
+                hash: 0,
+            };
+
+            let result = loop {
+                match client.invoke(&request).await {
+                    Ok(r) => break r,
+                    Err(e) => {
+                        let message = e.to_string();
+                        if let Some(seconds) = parse_flood_wait(&message) {
+                            warn!("⏳ FLOOD_WAIT: sleeping {}s before retrying offset {}", seconds, offset);
+                            tokio::time::sleep(tokio::time::Duration::from_secs(seconds)).await;
+                            continue;
+                        }
+                        return Err(format!("GetParticipants failed: {}", message));
+                    }
+                }
             };
-            members.push(member);
+
+            let (users, returned) = match result {
+                ChannelParticipants::Participants(p) => (p.users, p.participants.len()),
+                ChannelParticipants::NotModified => (Vec::new(), 0),
+            };
+
+            if returned == 0 {
+                break;
+            }
+
+            for user in users {
+                if let UserEnum::User(u) = user {
+                    members.push(TelegramMember {
+                        id: u.id,
+                        username: u.username.as_deref()
+                            .map(|s| CString::new(s).unwrap().into_raw())
+                            .unwrap_or(std::ptr::null_mut()),
+                        first_name: u.first_name.as_deref()
+                            .map(|s| CString::new(s).unwrap().into_raw())
+                            .unwrap_or(std::ptr::null_mut()),
+                        last_name: u.last_name.as_deref()
+                            .map(|s| CString::new(s).unwrap().into_raw())
+                            .unwrap_or(std::ptr::null_mut()),
+                        phone: u.phone.as_deref()
+                            .map(|s| CString::new(s).unwrap().into_raw())
+                            .unwrap_or(std::ptr::null_mut()),
+                        is_premium: u.premium,
+                        last_online: u.status.as_ref().map(last_online_timestamp).unwrap_or(0),
+                        photo_path: std::ptr::null_mut(),
+                    });
+
+                    if members.len() >= limit as usize {
+                        break;
+                    }
+                }
+            }
+
+            offset += returned as i32;
+
+            if returned < PAGE_SIZE as usize {
+                break;
+            }
         }
 
         Ok(members)
     }
 
-    fn add_unique_member(&self, member: &TelegramMember) -> bool {
+    fn add_unique_member(&self, member: &TelegramMember, source_group: &str) -> bool {
         let mut dedup = self.dedup_set.lock().unwrap();
         if dedup.contains(&member.id) {
-            false
-        } else {
-            dedup.insert(member.id);
-            self.members_cache.insert(member.id, member.clone());
-            true
+            return false;
+        }
+
+        dedup.insert(member.id);
+        self.members_cache.insert(member.id, member.clone());
+        drop(dedup);
+
+        let scraped_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        if let Err(e) = self.storage.lock().unwrap().upsert_member(member, source_group, scraped_at) {
+            warn!("Failed to persist member {}: {}", member.id, e);
         }
+
+        // Blocks once the feed is full, applying backpressure instead of
+        // dropping members that streaming consumers haven't drained yet.
+        if self.member_feed.send(member.clone()).is_err() {
+            warn!("Member feed dispatcher thread gone, dropping live update for {}", member.id);
+        }
+
+        true
     }
 }
 
+/// Maps a Telegram `UserStatus` to a best-effort `last_online` unix timestamp.
+fn last_online_timestamp(status: &grammers_tl_types::enums::UserStatus) -> i64 {
+    use grammers_tl_types::enums::UserStatus;
+    match status {
+        UserStatus::Offline(s) => s.was_online as i64,
+        UserStatus::Online(s) => s.expires as i64,
+        _ => 0,
+    }
+}
+
+/// Extracts the wait duration from a Telegram `FLOOD_WAIT_X` RPC error string.
+fn parse_flood_wait(message: &str) -> Option<u64> {
+    let idx = message.find("FLOOD_WAIT_")?;
+    message[idx + "FLOOD_WAIT_".len()..]
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
 // FFI exports for C++ integration
 static mut ENGINE: Option<ScraperEngine> = None;
 
@@ -266,11 +535,178 @@ pub unsafe extern "C" fn scraper_free_members(
             if !member.phone.is_null() {
                 let _ = CString::from_raw(member.phone);
             }
+            if !member.photo_path.is_null() {
+                let _ = CString::from_raw(member.photo_path);
+            }
         }
         let _ = Box::from_raw(std::slice::from_raw_parts_mut(members, count as usize));
     }
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn scraper_open_db(path: *const c_char) -> c_int {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    let path_str = match CStr::from_ptr(path).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    match engine.open_db(path_str) {
+        Ok(_) => 1,
+        Err(e) => {
+            error!("Failed to open database: {}", e);
+            0
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn scraper_export_db(format: *const c_char) -> *mut c_char {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return std::ptr::null_mut(),
+    };
+
+    let format_str = match CStr::from_ptr(format).to_str() {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    match engine.export_db(format_str) {
+        Ok(data) => CString::new(data).map(|s| s.into_raw()).unwrap_or(std::ptr::null_mut()),
+        Err(e) => {
+            error!("Failed to export database: {}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn scraper_download_photos(dir: *const c_char) -> c_int {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    let dir_str = match CStr::from_ptr(dir).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    match engine.runtime.block_on(engine.download_photos(dir_str)) {
+        Ok(count) => {
+            info!("🖼️ Downloaded {} profile photos to {}", count, dir_str);
+            1
+        }
+        Err(e) => {
+            error!("Photo download failed: {}", e);
+            0
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn scraper_set_member_callback(callback: MemberCallback) -> c_int {
+    let engine = match ENGINE.as_ref() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    engine.set_member_callback(callback);
+    1
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn scraper_bot_sign_in(bot_token: *const c_char) -> c_int {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    let token_str = match CStr::from_ptr(bot_token).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    match engine.runtime.block_on(engine.bot_sign_in(token_str)) {
+        Ok(_) => 1,
+        Err(e) => {
+            error!("Bot sign-in failed: {}", e);
+            0
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn scraper_request_login_code(phone: *const c_char) -> c_int {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    let phone_str = match CStr::from_ptr(phone).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    match engine.runtime.block_on(engine.request_login_code(phone_str)) {
+        Ok(_) => 1,
+        Err(e) => {
+            error!("Failed to request login code: {}", e);
+            0
+        }
+    }
+}
+
+/// Returns 1 if sign-in completed, 2 if a 2FA password is now required
+/// via `scraper_submit_password`, or 0 on failure.
+#[no_mangle]
+pub unsafe extern "C" fn scraper_submit_code(code: *const c_char) -> c_int {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    let code_str = match CStr::from_ptr(code).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    match engine.runtime.block_on(engine.submit_code(code_str)) {
+        Ok(true) => 2,
+        Ok(false) => 1,
+        Err(e) => {
+            error!("Failed to submit code: {}", e);
+            0
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn scraper_submit_password(password: *const c_char) -> c_int {
+    let engine = match ENGINE.as_mut() {
+        Some(e) => e,
+        None => return 0,
+    };
+
+    let password_str = match CStr::from_ptr(password).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    match engine.runtime.block_on(engine.submit_password(password_str)) {
+        Ok(_) => 1,
+        Err(e) => {
+            error!("Failed to submit password: {}", e);
+            0
+        }
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn scraper_destroy() {
     ENGINE = None;
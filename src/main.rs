@@ -1,6 +1,8 @@
 //! Ultra-Fast Native Telegram Scraper v2.0
 //! 10x faster than Python - Pure Rust implementation
 
+mod bench;
+
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,6 +26,42 @@ struct Args {
     /// Show performance comparison
     #[arg(long)]
     benchmark: bool,
+
+    /// Write an empty placeholder `photos/<id>.jpg` for each scraped member
+    /// (the standalone CLI has no MTProto session, so no photo is fetched)
+    #[arg(long)]
+    download_photos: bool,
+
+    /// JSON workload file to benchmark (repeatable; runs sequentially)
+    #[arg(long)]
+    workload: Vec<String>,
+
+    /// Optional URL to POST structured benchmark results to
+    #[arg(long)]
+    report_url: Option<String>,
+
+    /// Stream newly-deduplicated members to stdout as newline-delimited JSON
+    #[arg(long)]
+    stream: bool,
+
+    /// Authenticate as a bot instead of a user account
+    #[arg(long)]
+    bot_token: Option<String>,
+
+    /// Phone number for interactive user sign-in when no session exists yet
+    #[arg(long)]
+    phone: Option<String>,
+}
+
+/// Reads a line of interactive input from stdin, e.g. a login code or a
+/// two-step verification password.
+fn prompt(label: &str) -> Result<String, Box<dyn std::error::Error>> {
+    use std::io::Write;
+    print!("{}: ", label);
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,7 +89,7 @@ impl NativeTelegramScraper {
         }
     }
 
-    async fn scrape_channel(&mut self, target: &str, max_members: u32) -> Result<Vec<TelegramMember>, String> {
+    async fn scrape_channel(&mut self, target: &str, max_members: u32, stream: bool) -> Result<Vec<TelegramMember>, String> {
         println!("🎯 Scraping: {} (max: {})", target, max_members);
         println!("🦀 Using native Rust implementation...");
 
@@ -84,8 +122,15 @@ impl NativeTelegramScraper {
                 // Deduplication
                 if !self.members_cache.contains_key(&member.id) {
                     self.members_cache.insert(member.id, member.clone());
+
+                    if stream {
+                        if let Ok(line) = serde_json::to_string(&member) {
+                            println!("{}", line);
+                        }
+                    }
+
                     members.push(member);
-                    
+
                     if members.len() >= max_members as usize {
                         break;
                     }
@@ -133,6 +178,41 @@ impl NativeTelegramScraper {
         Ok(())
     }
 
+    async fn download_photos(&self, members: &[TelegramMember], dir: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        use tokio::sync::Semaphore;
+        use std::sync::Arc;
+
+        println!("📸 Downloading profile photos to {}...", dir);
+        std::fs::create_dir_all(dir)?;
+
+        let semaphore = Arc::new(Semaphore::new(8));
+        let mut tasks = Vec::with_capacity(members.len());
+
+        for member in members {
+            let semaphore = Arc::clone(&semaphore);
+            let path = format!("{}/{}.jpg", dir, member.id);
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+                // Standalone CLI mode has no live MTProto session to fetch a real
+                // CDN file from, so this simulates the same bounded-concurrency
+                // download shape the native engine uses against Telegram.
+                sleep(Duration::from_millis(50)).await;
+                std::fs::write(&path, []).is_ok()
+            }));
+        }
+
+        let mut downloaded = 0;
+        for task in tasks {
+            if task.await.unwrap_or(false) {
+                downloaded += 1;
+            }
+        }
+
+        println!("✅ Saved {} placeholder profile photo files", downloaded);
+        Ok(downloaded)
+    }
+
     fn show_performance_stats(&self) {
         println!("\n🚀 NATIVE PERFORMANCE STATS:");
         println!("   • Language: 100% Rust (memory safe)");
@@ -146,25 +226,32 @@ impl NativeTelegramScraper {
     }
 }
 
-fn benchmark_performance() {
-    use std::time::Instant;
-    
-    println!("⚡ Running performance benchmark...");
-    
-    let start = Instant::now();
-    
-    // Simulate data processing
-    let mut data: Vec<u64> = Vec::new();
-    for i in 0..1_000_000 {
-        data.push(i * i);
+async fn run_benchmark(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let workloads = if args.workload.is_empty() {
+        vec![bench::Workload {
+            name: "default".to_string(),
+            target: args.target.clone(),
+            max_members: args.max_members,
+            iterations: 1,
+            concurrency: 1,
+        }]
+    } else {
+        bench::load_workloads(&args.workload)?
+    };
+
+    let mut results = Vec::with_capacity(workloads.len());
+    for workload in &workloads {
+        results.push(bench::run_workload(workload).await);
     }
-    
-    let sum: u64 = data.iter().sum();
-    let duration = start.elapsed();
-    
-    println!("🏁 Processed 1M items in {:?}", duration);
-    println!("📊 Sum: {} (validation)", sum);
-    println!("🚀 This is the power of native compilation!");
+
+    bench::print_results_table(&results);
+
+    if let Some(report_url) = &args.report_url {
+        bench::report_results(report_url, &results).await?;
+        println!("📡 Reported results to {}", report_url);
+    }
+
+    Ok(())
 }
 
 #[tokio::main]
@@ -175,19 +262,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("================================================");
     println!("Target: {} | Max: {} | Output: {}", args.target, args.max_members, args.output);
     
-    if args.benchmark {
-        benchmark_performance();
-        println!();
+    // The standalone CLI has no live MTProto session of its own - the real
+    // sign-in flow (including 2FA) runs in the native engine behind the FFI
+    // used by the C++ integration. This just collects the credentials a
+    // first-time user would otherwise have to hand-craft a session file for.
+    if let Some(_bot_token) = &args.bot_token {
+        println!("🤖 Using bot token authentication");
+    } else if let Some(phone) = &args.phone {
+        println!("📨 Phone sign-in for {}", phone);
+        let code = prompt("Enter the code you received")?;
+        if code.is_empty() {
+            return Err("Login code is required".into());
+        }
+        println!("✅ Code received (not verified by this CLI)");
     }
-    
+
+    if args.benchmark || !args.workload.is_empty() {
+        run_benchmark(&args).await?;
+        return Ok(());
+    }
+
     let mut scraper = NativeTelegramScraper::new();
     
     // Perform scraping
-    let members = scraper.scrape_channel(&args.target, args.max_members).await?;
+    let members = scraper.scrape_channel(&args.target, args.max_members, args.stream).await?;
     
     // Export results
     scraper.export_results(&members, &args.output)?;
-    
+
+    if args.download_photos {
+        scraper.download_photos(&members, "photos").await?;
+    }
+
     // Show performance statistics
     scraper.show_performance_stats();
     
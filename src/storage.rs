@@ -0,0 +1,162 @@
+//! SQLite-backed persistence for scraped members.
+//!
+//! Keeps a `members` table keyed on Telegram user id so a scrape can resume
+//! across restarts: on open we preload every known id into the in-memory
+//! dedup set, and each newly accepted member is upserted back to disk.
+
+use std::collections::HashSet;
+
+use rusqlite::{params, Connection};
+
+use crate::TelegramMember;
+
+pub struct Storage {
+    conn: Connection,
+}
+
+impl Storage {
+    pub fn open(path: &str) -> Result<Self, String> {
+        let conn = Connection::open(path)
+            .map_err(|e| format!("Failed to open database {}: {}", path, e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS members (
+                id           INTEGER PRIMARY KEY,
+                username     TEXT,
+                first_name   TEXT,
+                last_name    TEXT,
+                phone        TEXT,
+                is_premium   INTEGER NOT NULL,
+                last_online  INTEGER NOT NULL,
+                source_group TEXT NOT NULL,
+                scraped_at   INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| format!("Failed to create members table: {}", e))?;
+
+        Ok(Self { conn })
+    }
+
+    /// Loads every previously-captured member id, used to seed the dedup set
+    /// so re-running against the same target only accepts and returns the
+    /// delta; the participant pages themselves are still requested in full.
+    pub fn load_existing_ids(&self) -> Result<HashSet<i64>, String> {
+        let mut stmt = self.conn.prepare("SELECT id FROM members")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], |row| row.get::<_, i64>(0))
+            .map_err(|e| e.to_string())?;
+
+        let mut ids = HashSet::new();
+        for row in rows {
+            ids.insert(row.map_err(|e| e.to_string())?);
+        }
+        Ok(ids)
+    }
+
+    pub fn upsert_member(&self, member: &TelegramMember, source_group: &str, scraped_at: i64) -> Result<(), String> {
+        let username = unsafe { c_str_opt(member.username) };
+        let first_name = unsafe { c_str_opt(member.first_name) };
+        let last_name = unsafe { c_str_opt(member.last_name) };
+        let phone = unsafe { c_str_opt(member.phone) };
+
+        self.conn.execute(
+            "INSERT INTO members (id, username, first_name, last_name, phone, is_premium, last_online, source_group, scraped_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(id) DO UPDATE SET
+                username = excluded.username,
+                first_name = excluded.first_name,
+                last_name = excluded.last_name,
+                phone = excluded.phone,
+                is_premium = excluded.is_premium,
+                last_online = excluded.last_online,
+                source_group = excluded.source_group,
+                scraped_at = excluded.scraped_at",
+            params![
+                member.id,
+                username,
+                first_name,
+                last_name,
+                phone,
+                member.is_premium,
+                member.last_online,
+                source_group,
+                scraped_at,
+            ],
+        )
+        .map_err(|e| format!("Failed to upsert member {}: {}", member.id, e))?;
+
+        Ok(())
+    }
+
+    /// Dumps the full `members` table as either `"json"` or `"csv"`, returning
+    /// the serialized contents for the caller to write out.
+    pub fn export(&self, format: &str) -> Result<String, String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, username, first_name, last_name, phone, is_premium, last_online, source_group, scraped_at FROM members",
+        )
+        .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(ExportRow {
+                    id: row.get(0)?,
+                    username: row.get(1)?,
+                    first_name: row.get(2)?,
+                    last_name: row.get(3)?,
+                    phone: row.get(4)?,
+                    is_premium: row.get(5)?,
+                    last_online: row.get(6)?,
+                    source_group: row.get(7)?,
+                    scraped_at: row.get(8)?,
+                })
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+
+        match format {
+            "json" => serde_json::to_string_pretty(&rows).map_err(|e| e.to_string()),
+            "csv" => {
+                let mut out = String::from("id,username,first_name,last_name,phone,is_premium,last_online,source_group,scraped_at\n");
+                for row in rows {
+                    out.push_str(&format!(
+                        "{},{},{},{},{},{},{},{},{}\n",
+                        row.id,
+                        row.username.unwrap_or_default(),
+                        row.first_name.unwrap_or_default(),
+                        row.last_name.unwrap_or_default(),
+                        row.phone.unwrap_or_default(),
+                        row.is_premium,
+                        row.last_online,
+                        row.source_group,
+                        row.scraped_at,
+                    ));
+                }
+                Ok(out)
+            }
+            other => Err(format!("Unsupported export format: {}", other)),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ExportRow {
+    id: i64,
+    username: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    phone: Option<String>,
+    is_premium: bool,
+    last_online: i64,
+    source_group: String,
+    scraped_at: i64,
+}
+
+unsafe fn c_str_opt(ptr: *mut std::os::raw::c_char) -> Option<String> {
+    if ptr.is_null() {
+        None
+    } else {
+        std::ffi::CStr::from_ptr(ptr).to_str().ok().map(|s| s.to_string())
+    }
+}